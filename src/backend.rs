@@ -0,0 +1,446 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use std::path::{Path, PathBuf};
+use tokio::process::Command as AsyncCommand;
+
+use crate::command::CommandRunner;
+use crate::config::DEFAULT_HOST;
+use crate::git::GitRepository;
+
+/// Abstracts the git operations the commands need (clone, status,
+/// add/commit/push, repo discovery) so they can be driven against an
+/// in-memory mock in tests instead of a real git install and filesystem.
+#[async_trait]
+pub trait GitBackend: Send + Sync {
+    async fn clone(&self, url: &str, dest: &Path) -> Result<()>;
+    fn repo_exists(&self, path: &Path) -> bool;
+    /// Whether the tree root itself (e.g. `~/repo`) exists, checked before
+    /// walking it.
+    fn tree_root_exists(&self, path: &Path) -> bool;
+    /// Walks `<tree_root>/<owner>/<repo>` (tagged with the default host),
+    /// plus `<tree_root>/<host>/<owner>/<repo>` for each name in
+    /// `host_names` (the namespace directories non-default hosts clone
+    /// into), and returns every repo directory found as `(host, owner,
+    /// repo, path)`.
+    fn list_repo_dirs(
+        &self,
+        tree_root: &Path,
+        host_names: &[String],
+    ) -> Result<Vec<(String, String, String, PathBuf)>>;
+    /// A colored one-line status summary, e.g. "main Clean", "main ↑2 ↓1", or "Not a git repository".
+    fn status_line(&self, path: &Path) -> Result<String>;
+    async fn add_all(&self, path: &Path) -> Result<()>;
+    async fn has_staged_changes(&self, path: &Path) -> Result<bool>;
+    async fn commit(&self, path: &Path, message: &str) -> Result<()>;
+    async fn push(&self, path: &Path) -> Result<()>;
+    async fn pull(&self, path: &Path) -> Result<()>;
+    /// Checks out a local or remote-tracking branch, e.g. to honor a
+    /// `ManagedRepo`'s declared `branch` before syncing.
+    async fn checkout(&self, path: &Path, branch: &str) -> Result<()>;
+    fn branch_name(&self, path: &Path) -> Option<String>;
+    async fn head_rev(&self, path: &Path) -> Result<String>;
+    /// `git log <range> --oneline`, one entry per line.
+    async fn log_range(&self, path: &Path, range: &str) -> Result<Vec<String>>;
+}
+
+/// Returns the direct subdirectories of `dir`.
+fn subdirs(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut dirs = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            dirs.push(path);
+        }
+    }
+    Ok(dirs)
+}
+
+/// The real backend, shelling out to `git` and opening repos with `git2`.
+pub struct RealGitBackend {
+    /// Masks configured secrets (e.g. an HTTPS clone token) out of anything
+    /// `clone` logs or returns in its error text.
+    runner: CommandRunner,
+}
+
+impl RealGitBackend {
+    pub fn new(secrets: Vec<String>) -> Self {
+        Self {
+            runner: CommandRunner::new(secrets),
+        }
+    }
+}
+
+#[async_trait]
+impl GitBackend for RealGitBackend {
+    async fn clone(&self, url: &str, dest: &Path) -> Result<()> {
+        self.runner
+            .run_cmd(
+                "git",
+                &["clone", url, dest.to_str().unwrap()],
+                Path::new("."),
+                true,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    fn repo_exists(&self, path: &Path) -> bool {
+        path.exists()
+    }
+
+    fn tree_root_exists(&self, path: &Path) -> bool {
+        path.exists()
+    }
+
+    fn list_repo_dirs(
+        &self,
+        tree_root: &Path,
+        host_names: &[String],
+    ) -> Result<Vec<(String, String, String, PathBuf)>> {
+        let mut repos = Vec::new();
+
+        for entry in std::fs::read_dir(tree_root)? {
+            let entry = entry?;
+            let path = entry.path();
+
+            if !path.is_dir() {
+                continue;
+            }
+
+            let name = path.file_name().unwrap().to_string_lossy().to_string();
+
+            if host_names.iter().any(|h| h == &name) {
+                // A non-default host's namespace directory: one more level
+                // down before we reach owner/repo.
+                let host_name = name;
+                for owner_path in subdirs(&path)? {
+                    let owner_name = owner_path
+                        .file_name()
+                        .unwrap()
+                        .to_string_lossy()
+                        .to_string();
+                    for repo_path in subdirs(&owner_path)? {
+                        let repo_name =
+                            repo_path.file_name().unwrap().to_string_lossy().to_string();
+                        repos.push((host_name.clone(), owner_name.clone(), repo_name, repo_path));
+                    }
+                }
+                continue;
+            }
+
+            let owner_name = name;
+            for repo_path in subdirs(&path)? {
+                let repo_name = repo_path.file_name().unwrap().to_string_lossy().to_string();
+                repos.push((
+                    DEFAULT_HOST.to_string(),
+                    owner_name.clone(),
+                    repo_name,
+                    repo_path,
+                ));
+            }
+        }
+
+        Ok(repos)
+    }
+
+    fn status_line(&self, path: &Path) -> Result<String> {
+        use colored::*;
+
+        let repo = match GitRepository::open(path) {
+            Ok(repo) => repo,
+            Err(_) => return Ok("Not a git repository".to_string()),
+        };
+
+        let status = repo.status()?;
+        let summary = status.summary();
+
+        if status.ahead > 0 || status.dirty {
+            Ok(summary.red().to_string())
+        } else if status.behind > 0 {
+            Ok(summary.yellow().to_string())
+        } else {
+            Ok(summary.green().to_string())
+        }
+    }
+
+    async fn add_all(&self, path: &Path) -> Result<()> {
+        let output = AsyncCommand::new("git")
+            .args(["add", "."])
+            .current_dir(path)
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            let error = String::from_utf8_lossy(&output.stderr);
+            return Err(anyhow!("{}", error.trim()));
+        }
+
+        Ok(())
+    }
+
+    async fn has_staged_changes(&self, path: &Path) -> Result<bool> {
+        let output = AsyncCommand::new("git")
+            .args(["diff", "--cached", "--quiet"])
+            .current_dir(path)
+            .output()
+            .await?;
+
+        // `git diff --cached --quiet` exits 0 when there's nothing staged.
+        Ok(!output.status.success())
+    }
+
+    async fn commit(&self, path: &Path, message: &str) -> Result<()> {
+        let output = AsyncCommand::new("git")
+            .args(["commit", "-m", message])
+            .current_dir(path)
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            let error = String::from_utf8_lossy(&output.stderr);
+            return Err(anyhow!("{}", error.trim()));
+        }
+
+        Ok(())
+    }
+
+    async fn push(&self, path: &Path) -> Result<()> {
+        let output = AsyncCommand::new("git")
+            .args(["push"])
+            .current_dir(path)
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            let error = String::from_utf8_lossy(&output.stderr);
+            return Err(anyhow!("{}", error.trim()));
+        }
+
+        Ok(())
+    }
+
+    async fn pull(&self, path: &Path) -> Result<()> {
+        let output = AsyncCommand::new("git")
+            .args(["pull"])
+            .current_dir(path)
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            let error = String::from_utf8_lossy(&output.stderr);
+            return Err(anyhow!("{}", error.trim()));
+        }
+
+        Ok(())
+    }
+
+    async fn checkout(&self, path: &Path, branch: &str) -> Result<()> {
+        let output = AsyncCommand::new("git")
+            .args(["checkout", branch])
+            .current_dir(path)
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            let error = String::from_utf8_lossy(&output.stderr);
+            return Err(anyhow!("{}", error.trim()));
+        }
+
+        Ok(())
+    }
+
+    fn branch_name(&self, path: &Path) -> Option<String> {
+        GitRepository::open(path).ok()?.branch_name()
+    }
+
+    async fn head_rev(&self, path: &Path) -> Result<String> {
+        let output = AsyncCommand::new("git")
+            .args(["rev-parse", "HEAD"])
+            .current_dir(path)
+            .output()
+            .await?;
+
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    async fn log_range(&self, path: &Path, range: &str) -> Result<Vec<String>> {
+        let output = AsyncCommand::new("git")
+            .args(["log", range, "--oneline"])
+            .current_dir(path)
+            .output()
+            .await?;
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(|l| l.to_string())
+            .collect())
+    }
+}
+
+#[cfg(test)]
+pub mod mock {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    /// Pre-seeded git state for one repo directory in a [`MockGitBackend`].
+    #[derive(Clone, Default)]
+    pub struct MockRepo {
+        pub branch: String,
+        pub has_staged_changes: bool,
+        pub status_line: String,
+    }
+
+    /// An in-memory [`GitBackend`] for unit tests: no process spawns, no disk I/O.
+    pub struct MockGitBackend {
+        /// Whether `tree_root_exists` reports the tree root as present.
+        pub root_exists: bool,
+        /// The repo directories `list_repo_dirs` reports, as (host, owner, repo, path).
+        pub repo_dirs: Vec<(String, String, String, PathBuf)>,
+        /// Per-path git state, keyed by the same paths used in `repo_dirs`.
+        pub repos: Mutex<HashMap<PathBuf, MockRepo>>,
+        pub committed: Mutex<Vec<(PathBuf, String)>>,
+        pub pushed: Mutex<Vec<PathBuf>>,
+        pub pulled: Mutex<Vec<PathBuf>>,
+        pub checked_out: Mutex<Vec<(PathBuf, String)>>,
+        pub cloned: Mutex<Vec<(String, PathBuf)>>,
+    }
+
+    impl Default for MockGitBackend {
+        fn default() -> Self {
+            Self {
+                root_exists: true,
+                repo_dirs: Vec::new(),
+                repos: Mutex::new(HashMap::new()),
+                committed: Mutex::new(Vec::new()),
+                pushed: Mutex::new(Vec::new()),
+                pulled: Mutex::new(Vec::new()),
+                checked_out: Mutex::new(Vec::new()),
+                cloned: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    impl MockGitBackend {
+        /// Seeds a repo directory on the default host.
+        pub fn with_repo(self, owner: &str, repo: &str, path: &Path, state: MockRepo) -> Self {
+            self.with_repo_on_host(crate::config::DEFAULT_HOST, owner, repo, path, state)
+        }
+
+        /// Seeds a repo directory under a specific host's namespace.
+        pub fn with_repo_on_host(
+            mut self,
+            host: &str,
+            owner: &str,
+            repo: &str,
+            path: &Path,
+            state: MockRepo,
+        ) -> Self {
+            self.repo_dirs.push((
+                host.to_string(),
+                owner.to_string(),
+                repo.to_string(),
+                path.to_path_buf(),
+            ));
+            self.repos
+                .get_mut()
+                .unwrap()
+                .insert(path.to_path_buf(), state);
+            self
+        }
+    }
+
+    #[async_trait]
+    impl GitBackend for MockGitBackend {
+        async fn clone(&self, url: &str, dest: &Path) -> Result<()> {
+            self.cloned
+                .lock()
+                .unwrap()
+                .push((url.to_string(), dest.to_path_buf()));
+            Ok(())
+        }
+
+        fn repo_exists(&self, path: &Path) -> bool {
+            self.repos.lock().unwrap().contains_key(path)
+        }
+
+        fn tree_root_exists(&self, _path: &Path) -> bool {
+            self.root_exists
+        }
+
+        fn list_repo_dirs(
+            &self,
+            _tree_root: &Path,
+            _host_names: &[String],
+        ) -> Result<Vec<(String, String, String, PathBuf)>> {
+            Ok(self.repo_dirs.clone())
+        }
+
+        fn status_line(&self, path: &Path) -> Result<String> {
+            Ok(self
+                .repos
+                .lock()
+                .unwrap()
+                .get(path)
+                .map(|r| r.status_line.clone())
+                .unwrap_or_else(|| "Not a git repository".to_string()))
+        }
+
+        async fn add_all(&self, _path: &Path) -> Result<()> {
+            Ok(())
+        }
+
+        async fn has_staged_changes(&self, path: &Path) -> Result<bool> {
+            Ok(self
+                .repos
+                .lock()
+                .unwrap()
+                .get(path)
+                .map(|r| r.has_staged_changes)
+                .unwrap_or(false))
+        }
+
+        async fn commit(&self, path: &Path, message: &str) -> Result<()> {
+            self.committed
+                .lock()
+                .unwrap()
+                .push((path.to_path_buf(), message.to_string()));
+            Ok(())
+        }
+
+        async fn push(&self, path: &Path) -> Result<()> {
+            self.pushed.lock().unwrap().push(path.to_path_buf());
+            Ok(())
+        }
+
+        async fn pull(&self, path: &Path) -> Result<()> {
+            self.pulled.lock().unwrap().push(path.to_path_buf());
+            Ok(())
+        }
+
+        async fn checkout(&self, path: &Path, branch: &str) -> Result<()> {
+            self.checked_out
+                .lock()
+                .unwrap()
+                .push((path.to_path_buf(), branch.to_string()));
+            Ok(())
+        }
+
+        fn branch_name(&self, path: &Path) -> Option<String> {
+            self.repos
+                .lock()
+                .unwrap()
+                .get(path)
+                .map(|r| r.branch.clone())
+        }
+
+        async fn head_rev(&self, _path: &Path) -> Result<String> {
+            Ok("deadbeef".to_string())
+        }
+
+        async fn log_range(&self, _path: &Path, _range: &str) -> Result<Vec<String>> {
+            Ok(vec!["deadbee commit subject".to_string()])
+        }
+    }
+}
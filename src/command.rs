@@ -0,0 +1,59 @@
+use anyhow::{anyhow, Result};
+use std::path::Path;
+use tokio::process::Command as AsyncCommand;
+
+/// Runs shell commands while masking configured secrets out of anything
+/// printed or returned in error text, so tokens never end up in logs.
+pub struct CommandRunner {
+    secrets: Vec<String>,
+}
+
+impl CommandRunner {
+    pub fn new(secrets: Vec<String>) -> Self {
+        Self {
+            secrets: secrets.into_iter().filter(|s| !s.is_empty()).collect(),
+        }
+    }
+
+    /// Masks any configured secrets out of `text`.
+    pub fn mask_text(&self, text: &str) -> String {
+        let mut masked = text.to_string();
+        for secret in &self.secrets {
+            masked = masked.replace(secret.as_str(), "***");
+        }
+        masked
+    }
+
+    /// Runs `cmd` with `args` in `dir`. When `logging` is set, prints the
+    /// (masked) invocation before running it. Returns masked stdout on
+    /// success, or a masked error on failure.
+    pub async fn run_cmd(
+        &self,
+        cmd: &str,
+        args: &[&str],
+        dir: &Path,
+        logging: bool,
+    ) -> Result<String> {
+        if logging {
+            println!(
+                "{}",
+                self.mask_text(&format!("$ {} {}", cmd, args.join(" ")))
+            );
+        }
+
+        let output = AsyncCommand::new(cmd)
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .await?;
+
+        let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(anyhow!("{}", self.mask_text(stderr.trim())));
+        }
+
+        Ok(self.mask_text(&stdout))
+    }
+}
@@ -0,0 +1,179 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+/// A single repository declared in the config file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManagedRepo {
+    pub owner: String,
+    pub repo: String,
+    /// Branch to track when syncing; defaults to the repo's current branch if unset.
+    pub branch: Option<String>,
+    /// Override for where this repo lives on disk, instead of `<tree root>/<owner>/<repo>`.
+    pub root: Option<String>,
+    /// Host/forge this repo lives on, as configured in `hosts`. Defaults to GitHub.
+    #[serde(default = "default_host_name")]
+    pub host: String,
+}
+
+fn default_host_name() -> String {
+    DEFAULT_HOST.to_string()
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Config {
+    #[serde(default)]
+    pub repos: Vec<ManagedRepo>,
+    /// Access token for HTTPS cloning, used when no `REPMAN_TOKEN` env var is set.
+    #[serde(default)]
+    pub token: Option<String>,
+    /// Git hosts/forges beyond the built-in GitHub default.
+    #[serde(default)]
+    pub hosts: Vec<HostConfig>,
+    /// Push notification sink, opt-in and disabled (`None`) by default.
+    #[serde(default)]
+    pub notify: Option<NotifyConfig>,
+}
+
+/// Where to send a notification after `sync` pushes successfully.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotifyConfig {
+    Webhook {
+        url: String,
+    },
+    Email {
+        smtp_host: String,
+        smtp_port: u16,
+        from: String,
+        to: String,
+        /// SMTP auth, if the relay requires it. Sent over TLS; never logged.
+        #[serde(default)]
+        username: Option<String>,
+        #[serde(default)]
+        password: Option<String>,
+    },
+}
+
+/// The scheme used to build a clone URL for a host.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CloneScheme {
+    #[default]
+    Ssh,
+    Https,
+}
+
+/// A configured git host/forge, e.g. a self-hosted GitLab or Forgejo instance.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HostConfig {
+    /// Short name used with `--host`/`-R`, e.g. "gitlab".
+    pub name: String,
+    /// Host part of the clone URL, e.g. "gitlab.com" or "git.example.com".
+    pub base_url: String,
+    #[serde(default)]
+    pub scheme: CloneScheme,
+}
+
+/// The built-in default host, used when `--host` is omitted and no config
+/// override named "github" exists.
+pub const DEFAULT_HOST: &str = "github";
+
+fn default_host_config() -> HostConfig {
+    HostConfig {
+        name: DEFAULT_HOST.to_string(),
+        base_url: "github.com".to_string(),
+        scheme: CloneScheme::Ssh,
+    }
+}
+
+/// Names of the configured hosts that get their own namespace directory on
+/// disk (every host except the default), as used by [`ManagedRepo::path`]
+/// and by `list_repo_dirs` to know which top-level directories are hosts
+/// rather than owners.
+pub fn namespaced_host_names(config: &Config) -> Vec<String> {
+    config
+        .hosts
+        .iter()
+        .map(|h| h.name.clone())
+        .filter(|name| name != DEFAULT_HOST)
+        .collect()
+}
+
+/// Resolves a `--host` name to its configuration, falling back to the
+/// built-in GitHub default if it isn't overridden in the config.
+pub fn resolve_host(config: &Config, name: &str) -> Result<HostConfig> {
+    if let Some(host) = config.hosts.iter().find(|h| h.name == name) {
+        return Ok(host.clone());
+    }
+
+    if name == DEFAULT_HOST {
+        return Ok(default_host_config());
+    }
+
+    Err(anyhow!(
+        "Unknown host '{}': add it to {}",
+        name,
+        config_path()?.display()
+    ))
+}
+
+/// Returns `~/.config/repman/repos.toml`.
+pub fn config_path() -> Result<PathBuf> {
+    let config_dir =
+        dirs::config_dir().ok_or_else(|| anyhow!("Could not find config directory"))?;
+    Ok(config_dir.join("repman").join("repos.toml"))
+}
+
+/// Loads the config file, returning an empty config if it doesn't exist yet.
+pub fn load_config() -> Result<Config> {
+    let path = config_path()?;
+    if !path.exists() {
+        return Ok(Config::default());
+    }
+
+    let contents = fs::read_to_string(&path)?;
+    let config: Config = toml::from_str(&contents)?;
+    Ok(config)
+}
+
+pub fn save_config(config: &Config) -> Result<()> {
+    let path = config_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let contents = toml::to_string_pretty(config)?;
+    fs::write(&path, contents)?;
+    Ok(())
+}
+
+/// Resolves the access token to use for HTTPS cloning: `REPMAN_TOKEN` takes
+/// priority over the config file so CI environments can override it without
+/// touching disk.
+pub fn access_token(config: &Config) -> Option<String> {
+    std::env::var("REPMAN_TOKEN")
+        .ok()
+        .filter(|t| !t.is_empty())
+        .or_else(|| config.token.clone())
+}
+
+impl ManagedRepo {
+    /// Resolves the on-disk path for this repo, honoring `root` if set and
+    /// namespacing non-default hosts under their own directory.
+    pub fn path(&self, tree_root: &std::path::Path) -> PathBuf {
+        if let Some(root) = &self.root {
+            return PathBuf::from(root);
+        }
+
+        if self.host == DEFAULT_HOST {
+            tree_root.join(&self.owner).join(&self.repo)
+        } else {
+            tree_root
+                .join(&self.host)
+                .join(&self.owner)
+                .join(&self.repo)
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::config::{HostConfig, DEFAULT_HOST};
+
+/// An issue (or pull request, which GitHub's API represents the same way).
+#[derive(Debug, Clone, Deserialize)]
+pub struct Issue {
+    pub number: u64,
+    pub title: String,
+    pub state: String,
+    pub html_url: String,
+}
+
+#[derive(Debug, Serialize)]
+struct CreateIssueRequest<'a> {
+    title: &'a str,
+    body: &'a str,
+}
+
+/// A thin REST client for a repo's forge (GitHub, or a Forgejo/Gitea-style
+/// instance), used by the `issue` subcommand.
+pub struct ForgeClient {
+    client: reqwest::Client,
+    api_base: String,
+    token: Option<String>,
+}
+
+impl ForgeClient {
+    pub fn new(host: &HostConfig, token: Option<String>) -> Self {
+        let api_base = if host.name == DEFAULT_HOST {
+            "https://api.github.com".to_string()
+        } else {
+            format!("https://{}/api/v1", host.base_url)
+        };
+
+        Self {
+            client: reqwest::Client::new(),
+            api_base,
+            token,
+        }
+    }
+
+    fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        let url = format!("{}{}", self.api_base, path);
+        let mut builder = self
+            .client
+            .request(method, url)
+            .header("User-Agent", "repman");
+
+        if let Some(token) = &self.token {
+            builder = builder.header("Authorization", format!("Bearer {}", token));
+        }
+
+        builder
+    }
+
+    pub async fn list_issues(&self, owner: &str, repo: &str) -> Result<Vec<Issue>> {
+        let response = self
+            .request(
+                reqwest::Method::GET,
+                &format!("/repos/{}/{}/issues", owner, repo),
+            )
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow!("Failed to list issues: {}", response.status()));
+        }
+
+        Ok(response.json().await?)
+    }
+
+    pub async fn view_issue(&self, owner: &str, repo: &str, number: u64) -> Result<Issue> {
+        let response = self
+            .request(
+                reqwest::Method::GET,
+                &format!("/repos/{}/{}/issues/{}", owner, repo, number),
+            )
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow!(
+                "Failed to fetch issue #{}: {}",
+                number,
+                response.status()
+            ));
+        }
+
+        Ok(response.json().await?)
+    }
+
+    pub async fn create_issue(
+        &self,
+        owner: &str,
+        repo: &str,
+        title: &str,
+        body: &str,
+    ) -> Result<Issue> {
+        let response = self
+            .request(
+                reqwest::Method::POST,
+                &format!("/repos/{}/{}/issues", owner, repo),
+            )
+            .json(&CreateIssueRequest { title, body })
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow!("Failed to create issue: {}", response.status()));
+        }
+
+        Ok(response.json().await?)
+    }
+}
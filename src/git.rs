@@ -0,0 +1,118 @@
+use anyhow::{anyhow, Result};
+use git2::{Repository, StatusOptions, Statuses};
+use std::path::Path;
+
+/// Thin wrapper around a `git2::Repository`, opened once per repo instead of
+/// shelling out to the `git` binary for every query.
+pub struct GitRepository {
+    repo: Repository,
+}
+
+/// Ahead/behind commit counts plus a clean/dirty flag for a repo's working tree.
+#[derive(Debug, Clone)]
+pub struct RepoStatus {
+    pub branch: Option<String>,
+    pub ahead: usize,
+    pub behind: usize,
+    pub dirty: bool,
+}
+
+impl RepoStatus {
+    /// Renders a compact summary like "main ↑2 ↓1" or "main Clean".
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if self.ahead > 0 {
+            parts.push(format!("↑{}", self.ahead));
+        }
+        if self.behind > 0 {
+            parts.push(format!("↓{}", self.behind));
+        }
+        if self.dirty {
+            parts.push("dirty".to_string());
+        }
+
+        let state = if parts.is_empty() {
+            "Clean".to_string()
+        } else {
+            parts.join(" ")
+        };
+
+        match &self.branch {
+            Some(branch) => format!("{} {}", branch, state),
+            None => state,
+        }
+    }
+}
+
+impl GitRepository {
+    pub fn open(path: &Path) -> Result<Self> {
+        let repo = Repository::open(path)?;
+        Ok(Self { repo })
+    }
+
+    /// The short name of the branch HEAD points at, or `None` for a detached HEAD.
+    pub fn branch_name(&self) -> Option<String> {
+        let head = self.repo.head().ok()?;
+        if !head.is_branch() {
+            return None;
+        }
+        head.shorthand().map(|s| s.to_string())
+    }
+
+    /// Commits the local branch is ahead/behind its upstream, or `(0, 0)` if
+    /// there's no upstream configured.
+    pub fn ahead_behind(&self) -> Result<(usize, usize)> {
+        let head = match self.repo.head() {
+            Ok(head) => head,
+            Err(_) => return Ok((0, 0)),
+        };
+
+        let local_oid = match head.target() {
+            Some(oid) => oid,
+            None => return Ok((0, 0)),
+        };
+
+        let local_branch_name = match head.shorthand() {
+            Some(name) => name,
+            None => return Ok((0, 0)),
+        };
+
+        let local_branch = self
+            .repo
+            .find_branch(local_branch_name, git2::BranchType::Local)?;
+
+        let upstream = match local_branch.upstream() {
+            Ok(upstream) => upstream,
+            Err(_) => return Ok((0, 0)),
+        };
+
+        let upstream_oid = upstream
+            .get()
+            .target()
+            .ok_or_else(|| anyhow!("upstream branch has no target"))?;
+
+        let (ahead, behind) = self.repo.graph_ahead_behind(local_oid, upstream_oid)?;
+        Ok((ahead, behind))
+    }
+
+    fn statuses(&self) -> Result<Statuses<'_>> {
+        let mut opts = StatusOptions::new();
+        opts.include_untracked(true);
+        Ok(self.repo.statuses(Some(&mut opts))?)
+    }
+
+    /// Whether the working tree has no uncommitted changes.
+    pub fn is_clean(&self) -> Result<bool> {
+        Ok(self.statuses()?.is_empty())
+    }
+
+    pub fn status(&self) -> Result<RepoStatus> {
+        let (ahead, behind) = self.ahead_behind()?;
+        Ok(RepoStatus {
+            branch: self.branch_name(),
+            ahead,
+            behind,
+            dirty: !self.is_clean()?,
+        })
+    }
+}
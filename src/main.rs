@@ -1,10 +1,21 @@
 use anyhow::{anyhow, Result};
 use clap::{Parser, Subcommand};
 use colored::*;
+use std::collections::HashSet;
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::process::Command;
-use tokio::process::Command as AsyncCommand;
+use std::sync::Arc;
+
+mod backend;
+mod command;
+mod config;
+mod forge;
+mod git;
+mod notify;
+
+use backend::{GitBackend, RealGitBackend};
+use config::load_config;
+use forge::ForgeClient;
 
 #[derive(Parser)]
 #[command(name = "repman")]
@@ -22,6 +33,9 @@ enum Commands {
         owner: String,
         /// Repository name
         repo: String,
+        /// Git host/forge to clone from, as configured in the config file (defaults to "github")
+        #[arg(short = 'R', long = "host", default_value = config::DEFAULT_HOST)]
+        host: String,
     },
     /// Show status of all repositories
     Status,
@@ -38,6 +52,52 @@ enum Commands {
         /// Repository name or owner/repo format
         name: String,
     },
+    /// Clone any repos from the config that aren't on disk yet, and pull the rest
+    SyncAll,
+    /// List repo directories on disk that aren't declared in the config
+    Unmanaged,
+    /// List, view, and create issues on the repo's forge
+    Issue {
+        #[command(subcommand)]
+        action: IssueCommands,
+    },
+}
+
+/// Common flags for selecting which repo an `issue` subcommand targets.
+#[derive(clap::Args)]
+struct RepoSelector {
+    /// Host/forge this repo lives on, as configured in the config file (defaults to the host
+    /// inferred from the current directory, or "github")
+    #[arg(short = 'R', long = "remote")]
+    remote: Option<String>,
+    /// Explicit owner/repo, overriding inference from the current directory
+    #[arg(long)]
+    repo: Option<String>,
+}
+
+#[derive(Subcommand)]
+enum IssueCommands {
+    /// List open issues
+    List {
+        #[command(flatten)]
+        selector: RepoSelector,
+    },
+    /// View a single issue by number
+    View {
+        #[command(flatten)]
+        selector: RepoSelector,
+        /// Issue number
+        number: u64,
+    },
+    /// Create a new issue
+    Create {
+        #[command(flatten)]
+        selector: RepoSelector,
+        #[arg(long)]
+        title: String,
+        #[arg(long)]
+        body: String,
+    },
 }
 
 fn get_repo_root() -> Result<PathBuf> {
@@ -54,275 +114,568 @@ fn ensure_repo_root_exists() -> Result<PathBuf> {
     Ok(repo_root)
 }
 
-async fn clone_repository(owner: &str, repo: &str) -> Result<()> {
+/// Clones `owner/repo` from `host`. `dest_override` pins the destination to
+/// a caller-resolved path (e.g. a managed repo's `root` override) instead of
+/// deriving the default `<tree root>/[host/]owner/repo` layout, and skips
+/// registering the repo in the config, since the caller already manages it.
+async fn clone_repository(
+    backend: &Arc<dyn GitBackend>,
+    owner: &str,
+    repo: &str,
+    host: &str,
+    dest_override: Option<&Path>,
+) -> Result<()> {
     let repo_root = ensure_repo_root_exists()?;
-    let owner_dir = repo_root.join(owner);
-    let repo_dir = owner_dir.join(repo);
-
-    if repo_dir.exists() {
-        println!("{} Repository already exists at: {}", "✓".green(), repo_dir.display());
+    let mut config = load_config()?;
+    let host_config = config::resolve_host(&config, host)?;
+
+    // Namespace non-default hosts under their own directory to avoid
+    // colliding with same-named owner/repo pairs on the default host; this
+    // is the same layout `ManagedRepo::path` uses for already-managed repos.
+    let repo_dir = match dest_override {
+        Some(dest) => dest.to_path_buf(),
+        None => config::ManagedRepo {
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+            branch: None,
+            root: None,
+            host: host_config.name.clone(),
+        }
+        .path(&repo_root),
+    };
+
+    if backend.repo_exists(&repo_dir) {
+        println!(
+            "{} Repository already exists at: {}",
+            "✓".green(),
+            repo_dir.display()
+        );
         return Ok(());
     }
 
-    // Create owner directory if it doesn't exist
-    if !owner_dir.exists() {
-        fs::create_dir_all(&owner_dir)?;
+    // Create the parent directory if it doesn't exist
+    if let Some(parent) = repo_dir.parent() {
+        if !parent.exists() {
+            fs::create_dir_all(parent)?;
+        }
     }
 
-    let repo_url = format!("git@github.com:{}/{}.git", owner, repo);
-    println!("Cloning {} to {}...", repo_url.cyan(), repo_dir.display());
-
-    let output = AsyncCommand::new("git")
-        .args(&["clone", &repo_url, repo_dir.to_str().unwrap()])
-        .output()
-        .await?;
+    let token = config::access_token(&config);
 
-    if !output.status.success() {
-        let error = String::from_utf8_lossy(&output.stderr);
-        return Err(anyhow!("Failed to clone repository: {}", error));
+    let repo_url = match (host_config.scheme, &token) {
+        (config::CloneScheme::Https, Some(token)) => format!(
+            "https://x-access-token:{}@{}/{}/{}.git",
+            token, host_config.base_url, owner, repo
+        ),
+        (config::CloneScheme::Https, None) => {
+            format!("https://{}/{}/{}.git", host_config.base_url, owner, repo)
+        }
+        (config::CloneScheme::Ssh, Some(token)) => format!(
+            "https://x-access-token:{}@{}/{}/{}.git",
+            token, host_config.base_url, owner, repo
+        ),
+        (config::CloneScheme::Ssh, None) => {
+            format!("git@{}:{}/{}.git", host_config.base_url, owner, repo)
+        }
+    };
+
+    println!(
+        "Cloning {}/{} from {} to {}...",
+        owner.cyan(),
+        repo.cyan(),
+        host_config.name.cyan(),
+        repo_dir.display()
+    );
+
+    GitBackend::clone(backend.as_ref(), &repo_url, &repo_dir)
+        .await
+        .map_err(|e| anyhow!("Failed to clone repository: {}", e))?;
+
+    println!(
+        "{} Successfully cloned to: {}",
+        "✓".green(),
+        repo_dir.display()
+    );
+    println!(
+        "Navigate to: {}",
+        format!("cd {}", repo_dir.display()).yellow()
+    );
+
+    // A bare `add` (no caller-resolved dest) isn't managed yet; persist it so
+    // `sync-all`/`unmanaged` know about it from now on.
+    if dest_override.is_none()
+        && !config
+            .repos
+            .iter()
+            .any(|r| r.owner == owner && r.repo == repo && r.host == host_config.name)
+    {
+        config.repos.push(config::ManagedRepo {
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+            branch: None,
+            root: None,
+            host: host_config.name.clone(),
+        });
+        config::save_config(&config)?;
     }
 
-    println!("{} Successfully cloned to: {}", "✓".green(), repo_dir.display());
-    println!("Navigate to: {}", format!("cd {}", repo_dir.display()).yellow());
-    
     Ok(())
 }
 
-fn get_git_status(repo_path: &Path) -> Result<String> {
-    let output = Command::new("git")
-        .args(&["status", "--porcelain", "--branch"])
-        .current_dir(repo_path)
-        .output()?;
+async fn show_status(backend: &Arc<dyn GitBackend>) -> Result<()> {
+    let repo_root = get_repo_root()?;
+    if !backend.tree_root_exists(&repo_root) {
+        println!(
+            "Repository root directory does not exist: {}",
+            repo_root.display()
+        );
+        return Ok(());
+    }
+
+    println!("{}", "Repository Status:".bold());
+    println!();
 
-    if !output.status.success() {
-        return Ok("Not a git repository".to_string());
+    let config = load_config()?;
+    let host_names = config::namespaced_host_names(&config);
+    let repos = backend.list_repo_dirs(&repo_root, &host_names)?;
+    if repos.is_empty() {
+        println!("No repositories found in {}", repo_root.display());
+        return Ok(());
     }
 
-    let status_output = String::from_utf8_lossy(&output.stdout);
-    let lines: Vec<&str> = status_output.lines().collect();
-    
-    if lines.is_empty() {
-        return Ok("Clean".green().to_string());
+    // status_line blocks on disk/process I/O, so run all repos concurrently
+    // instead of stalling through them one at a time.
+    let tasks = repos
+        .into_iter()
+        .map(|(_host_name, owner_name, repo_name, repo_path)| {
+            let backend = Arc::clone(backend);
+            tokio::task::spawn_blocking(move || {
+                let status = backend
+                    .status_line(&repo_path)
+                    .unwrap_or_else(|_| "Error".red().to_string());
+                (owner_name, repo_name, status)
+            })
+        });
+
+    let mut results: Vec<(String, String, String)> = futures::future::join_all(tasks)
+        .await
+        .into_iter()
+        .filter_map(|joined| joined.ok())
+        .collect();
+
+    results.sort_by(|a, b| (&a.0, &a.1).cmp(&(&b.0, &b.1)));
+
+    for (owner_name, repo_name, status) in results {
+        println!("{}/{} - {}", owner_name.cyan(), repo_name.bold(), status);
     }
 
-    // Check for ahead/behind status
-    if let Some(branch_line) = lines.first() {
-        if branch_line.contains("[ahead") {
-            return Ok("Ahead".red().to_string());
-        } else if branch_line.contains("[behind") {
-            return Ok("Behind".yellow().to_string());
-        }
+    Ok(())
+}
+
+/// Clones any repo declared in the config but missing on disk, and pulls the rest.
+async fn sync_all(backend: &Arc<dyn GitBackend>) -> Result<()> {
+    let repo_root = ensure_repo_root_exists()?;
+    let config = load_config()?;
+
+    if config.repos.is_empty() {
+        println!(
+            "No repositories declared in config: {}",
+            config::config_path()?.display()
+        );
+        return Ok(());
     }
 
-    // Check for uncommitted changes
-    let has_changes = lines.iter().skip(1).any(|line| !line.trim().is_empty());
-    if has_changes {
-        Ok("Dirty".red().to_string())
-    } else {
-        Ok("Clean".green().to_string())
+    for managed in &config.repos {
+        let repo_path = managed.path(&repo_root);
+
+        if !backend.repo_exists(&repo_path) {
+            println!(
+                "Cloning {}/{} (not yet present)...",
+                managed.owner.cyan(),
+                managed.repo.bold()
+            );
+            clone_repository(
+                backend,
+                &managed.owner,
+                &managed.repo,
+                &managed.host,
+                Some(&repo_path),
+            )
+            .await?;
+        }
+
+        if let Some(branch) = &managed.branch {
+            if let Err(e) = backend.checkout(&repo_path, branch).await {
+                println!(
+                    "{} Failed to checkout {} for {}/{}: {}",
+                    "✗".red(),
+                    branch,
+                    managed.owner,
+                    managed.repo,
+                    e
+                );
+                continue;
+            }
+        }
+
+        println!(
+            "Pulling {}/{}...",
+            managed.owner.cyan(),
+            managed.repo.bold()
+        );
+        if let Err(e) = backend.pull(&repo_path).await {
+            println!(
+                "{} Failed to pull {}/{}: {}",
+                "✗".red(),
+                managed.owner,
+                managed.repo,
+                e
+            );
+        } else {
+            println!(
+                "{} {}/{} up to date",
+                "✓".green(),
+                managed.owner,
+                managed.repo
+            );
+        }
     }
+
+    Ok(())
 }
 
-async fn show_status() -> Result<()> {
+/// Reports repo directories on disk that aren't declared in the config.
+async fn show_unmanaged(backend: &Arc<dyn GitBackend>) -> Result<()> {
     let repo_root = get_repo_root()?;
-    if !repo_root.exists() {
-        println!("Repository root directory does not exist: {}", repo_root.display());
+    if !backend.tree_root_exists(&repo_root) {
+        println!(
+            "Repository root directory does not exist: {}",
+            repo_root.display()
+        );
         return Ok(());
     }
 
-    println!("{}", "Repository Status:".bold());
-    println!();
+    let config = load_config()?;
+    let managed: HashSet<(String, String, String)> = config
+        .repos
+        .iter()
+        .map(|r| (r.host.clone(), r.owner.clone(), r.repo.clone()))
+        .collect();
 
-    let mut found_repos = false;
+    let mut found_unmanaged = false;
+    let host_names = config::namespaced_host_names(&config);
 
-    // Walk through owner directories
-    for owner_entry in fs::read_dir(&repo_root)? {
-        let owner_entry = owner_entry?;
-        let owner_path = owner_entry.path();
-        
-        if !owner_path.is_dir() {
+    for (host_name, owner_name, repo_name, repo_path) in
+        backend.list_repo_dirs(&repo_root, &host_names)?
+    {
+        if managed.contains(&(host_name, owner_name.clone(), repo_name.clone())) {
             continue;
         }
 
-        let owner_name = owner_path.file_name().unwrap().to_string_lossy();
-
-        // Walk through repository directories
-        for repo_entry in fs::read_dir(&owner_path)? {
-            let repo_entry = repo_entry?;
-            let repo_path = repo_entry.path();
-            
-            if !repo_path.is_dir() {
-                continue;
-            }
-
-            let repo_name = repo_path.file_name().unwrap().to_string_lossy();
-            let status = get_git_status(&repo_path).unwrap_or_else(|_| "Error".red().to_string());
-            
-            println!("{}/{} - {}", owner_name.cyan(), repo_name.bold(), status);
-            found_repos = true;
-        }
+        println!(
+            "{}/{} - {}",
+            owner_name.cyan(),
+            repo_name.bold(),
+            repo_path.display()
+        );
+        found_unmanaged = true;
     }
 
-    if !found_repos {
-        println!("No repositories found in {}", repo_root.display());
+    if !found_unmanaged {
+        println!("{} No unmanaged repositories found", "✓".green());
     }
 
     Ok(())
 }
 
-async fn sync_repository(name: &str, message: &str) -> Result<()> {
+/// Outcome of a [`sync_repository`] run, returned alongside the printed
+/// transcript so tests can assert on branching without scraping stdout.
+#[derive(Debug, PartialEq, Eq)]
+enum SyncOutcome {
+    NoChanges,
+    Synced,
+}
+
+async fn sync_repository(
+    backend: &Arc<dyn GitBackend>,
+    name: &str,
+    message: &str,
+) -> Result<SyncOutcome> {
     let repo_root = get_repo_root()?;
-    
-    // Find the repository by name (search in all owner directories)
-    let mut repo_path = None;
-    
-    for owner_entry in fs::read_dir(&repo_root)? {
-        let owner_entry = owner_entry?;
-        let owner_path = owner_entry.path();
-        
-        if !owner_path.is_dir() {
-            continue;
-        }
+    let host_names = config::namespaced_host_names(&load_config()?);
 
-        let potential_repo = owner_path.join(name);
-        if potential_repo.exists() && potential_repo.is_dir() {
-            repo_path = Some(potential_repo);
-            break;
-        }
-    }
+    // Find the repository by name (search in all owner directories)
+    let (owner_name, repo_path) = backend
+        .list_repo_dirs(&repo_root, &host_names)?
+        .into_iter()
+        .find(|(_, _, repo_name, _)| repo_name == name)
+        .map(|(_, owner, _, path)| (owner, path))
+        .ok_or_else(|| anyhow!("Repository '{}' not found", name))?;
 
-    let repo_path = repo_path.ok_or_else(|| anyhow!("Repository '{}' not found", name))?;
-    
     println!("Syncing repository: {}", repo_path.display());
 
     // Git add *
     println!("Adding all changes...");
-    let add_output = AsyncCommand::new("git")
-        .args(&["add", "."])
-        .current_dir(&repo_path)
-        .output()
-        .await?;
-
-    if !add_output.status.success() {
-        let error = String::from_utf8_lossy(&add_output.stderr);
-        return Err(anyhow!("Failed to add changes: {}", error));
-    }
+    backend
+        .add_all(&repo_path)
+        .await
+        .map_err(|e| anyhow!("Failed to add changes: {}", e))?;
 
-    // Check if there are changes to commit
-    let status_output = AsyncCommand::new("git")
-        .args(&["diff", "--cached", "--quiet"])
-        .current_dir(&repo_path)
-        .output()
-        .await?;
-
-    if status_output.status.success() {
+    if !backend.has_staged_changes(&repo_path).await? {
         println!("{} No changes to commit", "ℹ".blue());
-        return Ok(());
+        return Ok(SyncOutcome::NoChanges);
     }
 
     // Git commit
     println!("Committing with message: '{}'", message);
-    let commit_output = AsyncCommand::new("git")
-        .args(&["commit", "-m", message])
-        .current_dir(&repo_path)
-        .output()
-        .await?;
-
-    if !commit_output.status.success() {
-        let error = String::from_utf8_lossy(&commit_output.stderr);
-        return Err(anyhow!("Failed to commit: {}", error));
-    }
+    backend
+        .commit(&repo_path, message)
+        .await
+        .map_err(|e| anyhow!("Failed to commit: {}", e))?;
+
+    // Capture the commits about to be pushed so we can notify with them afterward.
+    let commits = backend.log_range(&repo_path, "@{u}..HEAD").await?;
 
     // Git push
     println!("Pushing to remote...");
-    let push_output = AsyncCommand::new("git")
-        .args(&["push"])
-        .current_dir(&repo_path)
-        .output()
-        .await?;
-
-    if !push_output.status.success() {
-        let error = String::from_utf8_lossy(&push_output.stderr);
-        return Err(anyhow!("Failed to push: {}", error));
-    }
+    backend
+        .push(&repo_path)
+        .await
+        .map_err(|e| anyhow!("Failed to push: {}", e))?;
 
     println!("{} Successfully synced repository!", "✓".green());
-    Ok(())
+
+    let branch = backend
+        .branch_name(&repo_path)
+        .unwrap_or_else(|| "HEAD".to_string());
+    let head = backend.head_rev(&repo_path).await?;
+
+    let config = load_config()?;
+    let push_notification = notify::PushNotification {
+        owner: owner_name,
+        repo: name.to_string(),
+        branch,
+        head,
+        commits,
+    };
+
+    if let Err(e) = notify::notify(&config.notify, &push_notification).await {
+        println!("{} Failed to send push notification: {}", "✗".red(), e);
+    }
+
+    Ok(SyncOutcome::Synced)
+}
+
+/// Outcome of a [`cd_repository`] run, returned alongside the printed
+/// transcript so tests can assert on branching without scraping stdout.
+#[derive(Debug, PartialEq, Eq)]
+enum CdOutcome {
+    NotFound,
+    Found(PathBuf),
+    Ambiguous(Vec<(String, String, PathBuf)>),
 }
 
-async fn cd_repository(name: &str) -> Result<()> {
+async fn cd_repository(backend: &Arc<dyn GitBackend>, name: &str) -> Result<CdOutcome> {
     let repo_root = get_repo_root()?;
-    if !repo_root.exists() {
-        println!("Repository root directory does not exist: {}", repo_root.display());
-        return Ok(());
+    if !backend.tree_root_exists(&repo_root) {
+        println!(
+            "Repository root directory does not exist: {}",
+            repo_root.display()
+        );
+        return Ok(CdOutcome::NotFound);
     }
 
-    // Check if it's owner/repo format
+    let host_names = config::namespaced_host_names(&load_config()?);
+
+    // Check if it's owner/repo format. Try the default host's layout first,
+    // then each non-default host's namespace directory, same as list_repo_dirs.
     if name.contains('/') {
         let parts: Vec<&str> = name.split('/').collect();
         if parts.len() == 2 {
             let owner = parts[0];
             let repo = parts[1];
-            let repo_path = repo_root.join(owner).join(repo);
-            
-            if repo_path.exists() {
+
+            let mut candidates = vec![repo_root.join(owner).join(repo)];
+            candidates.extend(
+                host_names
+                    .iter()
+                    .map(|host| repo_root.join(host).join(owner).join(repo)),
+            );
+
+            if let Some(repo_path) = candidates.into_iter().find(|p| backend.repo_exists(p)) {
                 println!("cd {}", repo_path.display());
-                return Ok(());
+                return Ok(CdOutcome::Found(repo_path));
             } else {
-                println!("{} Repository not found: {}", "✗".red(), repo_path.display());
-                return Ok(());
+                println!("{} Repository not found: {}/{}", "✗".red(), owner, repo);
+                return Ok(CdOutcome::NotFound);
             }
         }
     }
 
     // Search for repositories matching the name
     let mut matches = Vec::new();
-    
-    for owner_entry in fs::read_dir(&repo_root)? {
-        let owner_entry = owner_entry?;
-        let owner_path = owner_entry.path();
-        
-        if !owner_path.is_dir() {
-            continue;
-        }
-
-        let owner_name = owner_path.file_name().unwrap().to_string_lossy();
 
-        for repo_entry in fs::read_dir(&owner_path)? {
-            let repo_entry = repo_entry?;
-            let repo_path = repo_entry.path();
-            
-            if !repo_path.is_dir() {
-                continue;
-            }
-
-            let repo_name = repo_path.file_name().unwrap().to_string_lossy();
-            
-            // Exact match
-            if repo_name == name {
-                matches.push((owner_name.to_string(), repo_name.to_string(), repo_path.clone()));
-            }
-            // Fuzzy match (contains)
-            else if repo_name.to_lowercase().contains(&name.to_lowercase()) {
-                matches.push((owner_name.to_string(), repo_name.to_string(), repo_path.clone()));
-            }
+    for (_host_name, owner_name, repo_name, repo_path) in
+        backend.list_repo_dirs(&repo_root, &host_names)?
+    {
+        // Exact match, or fuzzy match (contains)
+        if repo_name == name || repo_name.to_lowercase().contains(&name.to_lowercase()) {
+            matches.push((owner_name, repo_name, repo_path));
         }
     }
 
-    match matches.len() {
+    let outcome = match matches.len() {
         0 => {
             println!("{} No repositories found matching '{}'", "✗".red(), name);
+            CdOutcome::NotFound
         }
         1 => {
-            let (_, _, path) = &matches[0];
+            let (_, _, path) = matches.into_iter().next().unwrap();
             println!("cd {}", path.display());
+            CdOutcome::Found(path)
         }
         _ => {
             println!("{} Multiple repositories found:", "ℹ".blue());
             for (i, (owner, repo, path)) in matches.iter().enumerate() {
-                println!("  {}: {}/{} -> {}", i + 1, owner.cyan(), repo.bold(), path.display());
+                println!(
+                    "  {}: {}/{} -> {}",
+                    i + 1,
+                    owner.cyan(),
+                    repo.bold(),
+                    path.display()
+                );
             }
             println!("\nUse the full format: repman cd owner/repo");
+            CdOutcome::Ambiguous(matches)
+        }
+    };
+
+    Ok(outcome)
+}
+
+/// Resolves the owner/repo a repo's subdirectory belongs to by stripping the
+/// tree root off the current directory. The leading component is treated as
+/// a host namespace (and stripped) when it names one of `host_names`;
+/// otherwise the repo is assumed to live directly under the default host's
+/// `<owner>/<repo>` layout.
+fn resolve_repo_from_cwd(
+    repo_root: &Path,
+    host_names: &[String],
+) -> Result<(Option<String>, String, String)> {
+    let cwd = std::env::current_dir()?;
+    let not_found = || {
+        anyhow!(
+            "Not inside a repo under {}; use --repo owner/repo",
+            repo_root.display()
+        )
+    };
+    let relative = cwd.strip_prefix(repo_root).map_err(|_| not_found())?;
+
+    let mut components = relative.components();
+    let first = components
+        .next()
+        .ok_or_else(not_found)?
+        .as_os_str()
+        .to_string_lossy()
+        .to_string();
+
+    let host = host_names.iter().find(|h| **h == first).cloned();
+    let owner = if host.is_some() {
+        components
+            .next()
+            .ok_or_else(not_found)?
+            .as_os_str()
+            .to_string_lossy()
+            .to_string()
+    } else {
+        first
+    };
+    let repo = components
+        .next()
+        .ok_or_else(not_found)?
+        .as_os_str()
+        .to_string_lossy()
+        .to_string();
+
+    Ok((host, owner, repo))
+}
+
+/// Resolves the owner/repo and host an `issue` subcommand targets, honoring
+/// `--repo` and `--remote` overrides before falling back to inferring both
+/// owner/repo and host from the current directory.
+fn resolve_issue_target(
+    config: &config::Config,
+    selector: &RepoSelector,
+) -> Result<(String, String, String)> {
+    if let Some(repo_arg) = &selector.repo {
+        let host = selector
+            .remote
+            .clone()
+            .unwrap_or_else(|| config::DEFAULT_HOST.to_string());
+        let parts: Vec<&str> = repo_arg.split('/').collect();
+        if parts.len() != 2 {
+            return Err(anyhow!("--repo must be in owner/repo format"));
+        }
+        return Ok((parts[0].to_string(), parts[1].to_string(), host));
+    }
+
+    let repo_root = get_repo_root()?;
+    let host_names = config::namespaced_host_names(config);
+    let (inferred_host, owner, repo) = resolve_repo_from_cwd(&repo_root, &host_names)?;
+    let host = selector
+        .remote
+        .clone()
+        .or(inferred_host)
+        .unwrap_or_else(|| config::DEFAULT_HOST.to_string());
+    Ok((owner, repo, host))
+}
+
+async fn handle_issue(action: IssueCommands) -> Result<()> {
+    let config = load_config()?;
+
+    let selector = match &action {
+        IssueCommands::List { selector } => selector,
+        IssueCommands::View { selector, .. } => selector,
+        IssueCommands::Create { selector, .. } => selector,
+    };
+
+    let (owner, repo, host_name) = resolve_issue_target(&config, selector)?;
+    let host_config = config::resolve_host(&config, &host_name)?;
+    let client = ForgeClient::new(&host_config, config::access_token(&config));
+
+    match action {
+        IssueCommands::List { .. } => {
+            let issues = client.list_issues(&owner, &repo).await?;
+            if issues.is_empty() {
+                println!("No open issues on {}/{}", owner, repo);
+            }
+            for issue in issues {
+                println!(
+                    "#{} {} [{}]",
+                    issue.number.to_string().cyan(),
+                    issue.title.bold(),
+                    issue.state
+                );
+            }
+        }
+        IssueCommands::View { number, .. } => {
+            let issue = client.view_issue(&owner, &repo, number).await?;
+            println!(
+                "#{} {} [{}]",
+                issue.number.to_string().cyan(),
+                issue.title.bold(),
+                issue.state
+            );
+            println!("{}", issue.html_url);
+        }
+        IssueCommands::Create { title, body, .. } => {
+            let issue = client.create_issue(&owner, &repo, &title, &body).await?;
+            println!(
+                "{} Created issue #{}: {}",
+                "✓".green(),
+                issue.number,
+                issue.html_url
+            );
         }
     }
 
@@ -332,21 +685,126 @@ async fn cd_repository(name: &str) -> Result<()> {
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
+    let config = load_config()?;
+    let secrets = config::access_token(&config).into_iter().collect();
+    let backend: Arc<dyn GitBackend> = Arc::new(RealGitBackend::new(secrets));
 
     match cli.command {
-        Commands::Add { owner, repo } => {
-            clone_repository(&owner, &repo).await?;
+        Commands::Add { owner, repo, host } => {
+            clone_repository(&backend, &owner, &repo, &host, None).await?;
         }
         Commands::Status => {
-            show_status().await?;
+            show_status(&backend).await?;
         }
         Commands::Sync { name, message } => {
-            sync_repository(&name, &message).await?;
+            sync_repository(&backend, &name, &message).await?;
         }
         Commands::Cd { name } => {
-            cd_repository(&name).await?;
+            cd_repository(&backend, &name).await?;
+        }
+        Commands::SyncAll => {
+            sync_all(&backend).await?;
+        }
+        Commands::Unmanaged => {
+            show_unmanaged(&backend).await?;
+        }
+        Commands::Issue { action } => {
+            handle_issue(action).await?;
         }
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use backend::mock::{MockGitBackend, MockRepo};
+
+    #[tokio::test]
+    async fn sync_with_no_changes_reports_no_commit() {
+        let repo_path = PathBuf::from("/fake/repo/acme/widgets");
+        let mock = Arc::new(MockGitBackend::default().with_repo(
+            "acme",
+            "widgets",
+            &repo_path,
+            MockRepo {
+                has_staged_changes: false,
+                ..Default::default()
+            },
+        ));
+        let backend: Arc<dyn GitBackend> = mock.clone();
+
+        let outcome = sync_repository(&backend, "widgets", "a commit message")
+            .await
+            .unwrap();
+
+        assert_eq!(outcome, SyncOutcome::NoChanges);
+        assert!(mock.committed.lock().unwrap().is_empty());
+        assert!(mock.pushed.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn sync_with_staged_changes_commits_and_pushes() {
+        let repo_path = PathBuf::from("/fake/repo/acme/widgets");
+        let mock = Arc::new(MockGitBackend::default().with_repo(
+            "acme",
+            "widgets",
+            &repo_path,
+            MockRepo {
+                has_staged_changes: true,
+                branch: "main".to_string(),
+                ..Default::default()
+            },
+        ));
+        let backend: Arc<dyn GitBackend> = mock.clone();
+
+        let outcome = sync_repository(&backend, "widgets", "a commit message")
+            .await
+            .unwrap();
+
+        assert_eq!(outcome, SyncOutcome::Synced);
+        assert_eq!(
+            mock.committed.lock().unwrap().as_slice(),
+            [(repo_path.clone(), "a commit message".to_string())]
+        );
+        assert_eq!(mock.pushed.lock().unwrap().as_slice(), [repo_path]);
+    }
+
+    #[tokio::test]
+    async fn cd_with_multiple_fuzzy_matches_lists_them() {
+        let mock = Arc::new(
+            MockGitBackend::default()
+                .with_repo(
+                    "acme",
+                    "widgets",
+                    &PathBuf::from("/fake/repo/acme/widgets"),
+                    MockRepo::default(),
+                )
+                .with_repo(
+                    "acme",
+                    "widgets-cli",
+                    &PathBuf::from("/fake/repo/acme/widgets-cli"),
+                    MockRepo::default(),
+                ),
+        );
+        let backend: Arc<dyn GitBackend> = mock.clone();
+
+        let outcome = cd_repository(&backend, "widgets").await.unwrap();
+
+        match outcome {
+            CdOutcome::Ambiguous(matches) => assert_eq!(matches.len(), 2),
+            other => panic!("expected Ambiguous, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn cd_with_no_matches_reports_not_found() {
+        let mock = Arc::new(MockGitBackend::default());
+        let backend: Arc<dyn GitBackend> = mock.clone();
+
+        let outcome = cd_repository(&backend, "nonexistent").await.unwrap();
+
+        assert_eq!(outcome, CdOutcome::NotFound);
+    }
+}
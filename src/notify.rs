@@ -0,0 +1,127 @@
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+
+use crate::config::NotifyConfig;
+
+/// Describes a successful push, gathered before the notifier sends it.
+#[derive(Debug, Serialize)]
+pub struct PushNotification {
+    pub owner: String,
+    pub repo: String,
+    pub branch: String,
+    /// New HEAD commit after the push.
+    pub head: String,
+    /// `git log @{u}..HEAD --oneline` output, captured before pushing.
+    pub commits: Vec<String>,
+}
+
+impl PushNotification {
+    fn subject_line(&self) -> String {
+        self.commits
+            .first()
+            .cloned()
+            .unwrap_or_else(|| "(no commits)".to_string())
+    }
+}
+
+/// Sends a push notification to the sink configured in `notify`, if any.
+/// A `None` config means notifications stay off, which is the default.
+pub async fn notify(config: &Option<NotifyConfig>, push: &PushNotification) -> Result<()> {
+    let Some(config) = config else {
+        return Ok(());
+    };
+
+    match config {
+        NotifyConfig::Webhook { url } => send_webhook(url, push).await,
+        NotifyConfig::Email {
+            smtp_host,
+            smtp_port,
+            from,
+            to,
+            username,
+            password,
+        } => {
+            send_email(
+                smtp_host,
+                *smtp_port,
+                from,
+                to,
+                username.as_deref(),
+                password.as_deref(),
+                push,
+            )
+            .await
+        }
+    }
+}
+
+async fn send_webhook(url: &str, push: &PushNotification) -> Result<()> {
+    let client = reqwest::Client::new();
+    client
+        .post(url)
+        .json(push)
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(())
+}
+
+/// Sends over a TLS relay connection: implicit TLS for the conventional
+/// `465` port, STARTTLS (the common submission setup, e.g. port `587`)
+/// otherwise. Authenticates with `username`/`password` when both are set.
+async fn send_email(
+    smtp_host: &str,
+    smtp_port: u16,
+    from: &str,
+    to: &str,
+    username: Option<&str>,
+    password: Option<&str>,
+    push: &PushNotification,
+) -> Result<()> {
+    let message = lettre::Message::builder()
+        .from(from.parse()?)
+        .to(to.parse()?)
+        .subject(format!(
+            "[{}/{}] pushed to {}: {}",
+            push.owner,
+            push.repo,
+            push.branch,
+            push.subject_line()
+        ))
+        .body(format!(
+            "{}/{} is now at {} on {}\n\n{}",
+            push.owner,
+            push.repo,
+            push.head,
+            push.branch,
+            push.commits.join("\n")
+        ))?;
+
+    let mut builder = if smtp_port == 465 {
+        lettre::SmtpTransport::relay(smtp_host)?
+    } else {
+        lettre::SmtpTransport::starttls_relay(smtp_host)?
+    }
+    .port(smtp_port);
+
+    match (username, password) {
+        (Some(username), Some(password)) => {
+            builder = builder.credentials(
+                lettre::transport::smtp::authentication::Credentials::new(
+                    username.to_string(),
+                    password.to_string(),
+                ),
+            );
+        }
+        (None, None) => {}
+        _ => {
+            return Err(anyhow!(
+                "email notify config must set both username and password, or neither"
+            ))
+        }
+    }
+    let transport = builder.build();
+
+    lettre::Transport::send(&transport, &message)?;
+    Ok(())
+}